@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use ngram_tools::iter::wordgrams::Wordgrams;
 use vector_space_model2::{
     build::IndexBuilder,
@@ -6,12 +8,15 @@ use vector_space_model2::{
     DefaultMetadata,
 };
 
-use crate::NGIndex;
+use crate::{NGIndex, QueryConfig};
 
 /// Helper to bulid a new NGIndex
 pub struct NGIndexBuilder<I: Decodable + Encodable> {
     builder: IndexBuilder<I>,
     n: usize,
+    synonyms: HashMap<String, Vec<String>>,
+    terms: HashMap<I, String>,
+    config: QueryConfig,
 }
 
 impl<I: Decodable + Encodable> NGIndexBuilder<I> {
@@ -19,19 +24,44 @@ impl<I: Decodable + Encodable> NGIndexBuilder<I> {
     #[inline]
     pub fn new(n: usize) -> Self {
         let builder = IndexBuilder::<I>::new();
-        Self { builder, n }
+        Self {
+            builder,
+            n,
+            synonyms: HashMap::new(),
+            terms: HashMap::new(),
+            config: QueryConfig::default(),
+        }
+    }
+
+    /// Registers `synonyms` for `term`, so any document inserted under `term` is also reachable
+    /// through its synonyms, and querying `term` also expands to its synonyms.
+    pub fn add_synonyms(&mut self, term: &str, synonyms: &[&str]) {
+        self.synonyms
+            .entry(term.to_string())
+            .or_default()
+            .extend(synonyms.iter().map(|s| s.to_string()));
+    }
+
+    /// Overrides the word separators and per-word padding behaviour used for multi-word queries.
+    pub fn set_query_config(&mut self, config: QueryConfig) {
+        self.config = config;
     }
 
     /// Inserts a new item that will later be included in the index
-    pub fn insert(&mut self, term: &str, id: I) -> bool {
+    pub fn insert(&mut self, term: &str, id: I) -> bool
+    where
+        I: Clone + Eq + std::hash::Hash,
+    {
         let term_len = term.chars().count();
         if term_len < self.n {
             return false;
         }
 
-        let padded = super::padded(term, self.n - 1);
-        let terms: Vec<_> = self.split_term(&padded).collect();
-        self.builder.insert_new_vec(id, &terms);
+        let mut grams = self.phrase_grams(term);
+        grams.extend(self.synonym_grams(term));
+
+        self.terms.insert(id.clone(), term.to_string());
+        self.builder.insert_new_vec(id, &grams);
 
         true
     }
@@ -42,11 +72,28 @@ impl<I: Decodable + Encodable> NGIndexBuilder<I> {
             .builder
             .build(DefaultMetadata::new(IndexVersion::V1))
             .unwrap();
-        NGIndex::new(index, self.n)
+        NGIndex::new(index, self.n, self.synonyms, self.terms, self.config)
     }
 
     #[inline]
     pub fn split_term<'a>(&self, term: &'a str) -> Wordgrams<'a> {
         Wordgrams::new(term, self.n)
     }
+
+    /// Pads `term` and splits it into its n-gram dimensions.
+    fn term_grams(&self, term: &str) -> Vec<String> {
+        crate::tokenize::term_grams(self.n, term)
+    }
+
+    /// Builds the n-gram terms for a (possibly multi-word) phrase, padding either each word
+    /// individually or the phrase as a whole depending on [`QueryConfig::padded_per_word`].
+    fn phrase_grams(&self, phrase: &str) -> Vec<String> {
+        crate::tokenize::phrase_grams(&self.config, self.n, phrase)
+    }
+
+    /// N-gram terms for every synonym registered for any word of `phrase`, looked up per word so
+    /// a synonym registered for `"school"` still applies inside `"to skip school"`.
+    fn synonym_grams(&self, phrase: &str) -> Vec<String> {
+        crate::tokenize::synonym_grams(&self.synonyms, &self.config, self.n, phrase)
+    }
 }
@@ -0,0 +1,163 @@
+//! A per-dimension posting-list cursor and a WAND-style merge used by
+//! [`NGIndex::find_top_k`](crate::NGIndex::find_top_k) to accumulate each candidate's overlap
+//! count (the dice numerator) in a single pass over sorted postings, skipping documents that
+//! can no longer reach the heap's current score threshold instead of visiting every document
+//! that shares any query dimension.
+
+/// A single dimension's postings (document id, document's total dimension count), sorted
+/// ascending by document id. The vector store's `get_all_iter` makes no ordering guarantee — `I`
+/// is an arbitrary caller-supplied key, not necessarily inserted ascending, and postings are at
+/// least as plausibly stored in insertion order — so [`new`](Self::new) sorts defensively rather
+/// than trusting the store.
+pub(crate) struct DocSetCursor<I> {
+    postings: Vec<(I, u32)>,
+    pos: usize,
+}
+
+impl<I: Ord> DocSetCursor<I> {
+    pub(crate) fn new(mut postings: Vec<(I, u32)>) -> Self {
+        postings.sort_by(|a, b| a.0.cmp(&b.0));
+        Self { postings, pos: 0 }
+    }
+
+    /// The document the cursor currently points at, or `None` if exhausted.
+    fn current(&self) -> Option<&(I, u32)> {
+        self.postings.get(self.pos)
+    }
+
+    /// Moves to the next document.
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    /// Moves forward to the first posting whose id is `>= target`, via exponential (galloping)
+    /// search for a bounding range followed by a binary search within it. Cheaper than repeated
+    /// [`advance`](Self::advance) calls when `target` is far ahead of the current position.
+    fn skip_to(&mut self, target: &I) {
+        match self.current() {
+            Some((id, _)) if id < target => {}
+            _ => return,
+        }
+
+        let mut lo = self.pos;
+        let mut step = 1;
+        loop {
+            let probe = (lo + step).min(self.postings.len());
+            if probe == self.postings.len() || &self.postings[probe].0 >= target {
+                self.pos = lo + self.postings[lo..probe].partition_point(|(id, _)| id < target);
+                return;
+            }
+            lo += step;
+            step *= 2;
+        }
+    }
+}
+
+/// Merges per-dimension [`DocSetCursor`]s into `(id, overlap, dimen_count)` triples in ascending
+/// id order, one pass over the sorted postings. The pivot document at each step is chosen the way
+/// WAND picks its pivot: cursors are sorted by current id and summed (each contributes at most
+/// one to the overlap count) until the running total reaches the required minimum overlap; any
+/// cursor that falls short of the pivot's id is [`skip_to`](DocSetCursor::skip_to) the pivot
+/// instead of being stepped one document at a time.
+pub(crate) struct DimMerge<I> {
+    cursors: Vec<DocSetCursor<I>>,
+    min_overlap: usize,
+}
+
+impl<I: Ord + Clone> DimMerge<I> {
+    pub(crate) fn new(cursors: Vec<DocSetCursor<I>>) -> Self {
+        Self {
+            cursors,
+            min_overlap: 1,
+        }
+    }
+
+    /// Raises the minimum overlap a future candidate must have to possibly be worth returning.
+    /// Once fewer cursors remain active than this, iteration stops early: no remaining document
+    /// can overlap with enough dimensions to reach it.
+    pub(crate) fn set_min_overlap(&mut self, min_overlap: usize) {
+        self.min_overlap = self.min_overlap.max(min_overlap);
+    }
+}
+
+impl<I: Ord + Clone> Iterator for DimMerge<I> {
+    type Item = (I, usize, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut active: Vec<usize> = (0..self.cursors.len())
+                .filter(|&i| self.cursors[i].current().is_some())
+                .collect();
+            if active.len() < self.min_overlap {
+                return None;
+            }
+            active.sort_by(|&a, &b| {
+                self.cursors[a].current().unwrap().0.cmp(&self.cursors[b].current().unwrap().0)
+            });
+
+            let pivot_idx = active[self.min_overlap - 1];
+            let pivot_id = self.cursors[pivot_idx].current().unwrap().0.clone();
+            let smallest_idx = active[0];
+
+            if self.cursors[smallest_idx].current().unwrap().0 != pivot_id {
+                self.cursors[smallest_idx].skip_to(&pivot_id);
+                continue;
+            }
+
+            let mut overlap = 0usize;
+            let mut dimen_count = None;
+            for idx in active {
+                let matched = match self.cursors[idx].current() {
+                    Some((id, count)) if *id == pivot_id => Some(*count),
+                    _ => None,
+                };
+                let Some(count) = matched else {
+                    break;
+                };
+
+                overlap += 1;
+                if dimen_count.is_none() {
+                    dimen_count = Some(count);
+                }
+                self.cursors[idx].advance();
+            }
+
+            return Some((pivot_id, overlap, dimen_count.unwrap()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor(postings: Vec<(u32, u32)>) -> DocSetCursor<u32> {
+        DocSetCursor::new(postings)
+    }
+
+    #[test]
+    fn merge_combines_overlap_even_when_postings_arrive_out_of_insertion_order() {
+        // Neither dimension's postings are sorted ascending by id, mirroring a vector store that
+        // returns postings in insertion order rather than id order.
+        let dim_a = cursor(vec![(5, 1), (1, 1), (3, 1)]);
+        let dim_b = cursor(vec![(1, 1), (3, 1)]);
+
+        let merged: Vec<_> = DimMerge::new(vec![dim_a, dim_b]).collect();
+
+        assert_eq!(
+            merged,
+            vec![(1, 2, 1), (3, 2, 1), (5, 1, 1)],
+            "doc 1 and doc 3 each occur in both dims and must merge into a single candidate \
+             with overlap 2, not two separate overlap-1 candidates"
+        );
+    }
+
+    #[test]
+    fn skip_to_still_finds_the_target_after_sorting_unordered_postings() {
+        let mut cur = cursor(vec![(9, 1), (2, 1), (6, 1)]);
+
+        cur.skip_to(&6);
+
+        assert_eq!(cur.current(), Some(&(6, 1)));
+    }
+}
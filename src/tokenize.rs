@@ -0,0 +1,53 @@
+//! N-gram tokenization helpers shared by [`NGIndexBuilder`](crate::builder::NGIndexBuilder) and
+//! [`NGIndex`](crate::NGIndex), so insert-time and query-time tokenization can't drift apart (as
+//! happened when `padded_per_word` needed fixing in both copies in the same commit).
+
+use std::collections::HashMap;
+
+use ngram_tools::iter::wordgrams::Wordgrams;
+
+use crate::{padded, QueryConfig};
+
+/// Splits `phrase` into words on `config`'s separators.
+pub(crate) fn words<'q>(config: &QueryConfig, phrase: &'q str) -> Vec<&'q str> {
+    phrase
+        .split(|c| config.separators.contains(&c))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Pads `term` and splits it into its `n`-gram dimensions.
+pub(crate) fn term_grams(n: usize, term: &str) -> Vec<String> {
+    let padded_term = padded(term, n - 1);
+    Wordgrams::new(&padded_term, n).map(|g| g.to_string()).collect()
+}
+
+/// Builds the n-gram terms for a (possibly multi-word) phrase, padding either each word
+/// individually or the phrase as a whole depending on `config.padded_per_word`.
+pub(crate) fn phrase_grams(config: &QueryConfig, n: usize, phrase: &str) -> Vec<String> {
+    if config.padded_per_word {
+        words(config, phrase)
+            .into_iter()
+            .flat_map(|w| term_grams(n, w))
+            .collect()
+    } else {
+        term_grams(n, phrase)
+    }
+}
+
+/// N-gram terms for every synonym registered for any word of `phrase` (not including `phrase`
+/// itself). Looked up per word rather than against the whole phrase, so a synonym registered for
+/// `"school"` still applies inside a multi-word phrase like `"to skip school"`.
+pub(crate) fn synonym_grams(
+    synonyms: &HashMap<String, Vec<String>>,
+    config: &QueryConfig,
+    n: usize,
+    phrase: &str,
+) -> Vec<String> {
+    words(config, phrase)
+        .into_iter()
+        .filter_map(|word| synonyms.get(word))
+        .flatten()
+        .flat_map(|synonym| phrase_grams(config, n, synonym))
+        .collect()
+}